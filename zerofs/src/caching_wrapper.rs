@@ -0,0 +1,364 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult, Result, path::Path,
+};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+type CacheKey = (Path, Range<u64>);
+
+/// In-memory LRU of byte ranges, evicting by total cached bytes.
+///
+/// Keys are exact `(Path, Range)` pairs: a lookup only hits when a previous
+/// read asked for the identical range. This keeps coherence trivial at the
+/// cost of not coalescing adjacent ranges.
+#[derive(Debug)]
+struct RangeCache {
+    entries: HashMap<CacheKey, Bytes>,
+    order: VecDeque<CacheKey>,
+    bytes: u64,
+    limit: u64,
+}
+
+impl RangeCache {
+    fn new(limit: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            bytes: 0,
+            limit,
+        }
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(pos).unwrap();
+            self.order.push_back(key);
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<Bytes> {
+        if let Some(bytes) = self.entries.get(key).cloned() {
+            self.touch(key);
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+
+    fn insert(&mut self, key: CacheKey, value: Bytes) {
+        // A single range larger than the whole budget is never cached.
+        if value.len() as u64 > self.limit {
+            return;
+        }
+        if let Some(old) = self.entries.insert(key.clone(), value.clone()) {
+            self.bytes -= old.len() as u64;
+            if let Some(pos) = self.order.iter().position(|k| k == &key) {
+                self.order.remove(pos);
+            }
+        }
+        self.bytes += value.len() as u64;
+        self.order.push_back(key);
+        while self.bytes > self.limit {
+            let Some(evicted) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(bytes) = self.entries.remove(&evicted) {
+                self.bytes -= bytes.len() as u64;
+            }
+        }
+    }
+
+    fn invalidate(&mut self, location: &Path) {
+        self.order.retain(|(path, _)| path != location);
+        self.entries.retain(|(path, _), bytes| {
+            if path == location {
+                self.bytes -= bytes.len() as u64;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// A point-in-time view of cache activity, for operator tuning.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub cached_bytes: u64,
+    pub limit_bytes: u64,
+}
+
+/// Cache state shared between the store and any in-flight multipart upload so
+/// the upload can invalidate the same caches when it completes.
+#[derive(Debug)]
+struct CacheShared {
+    cache: Mutex<RangeCache>,
+    heads: Mutex<HashMap<Path, ObjectMeta>>,
+    limit: u64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheShared {
+    fn invalidate(&self, location: &Path) {
+        self.cache.lock().invalidate(location);
+        self.heads.lock().remove(location);
+    }
+}
+
+/// ObjectStore decorator that serves repeated byte-range reads and `head`
+/// lookups from an in-memory LRU, evicting ranges by total cached bytes. Any
+/// mutation of a `Path` invalidates every cached range and the cached `head`
+/// for it so reads stay coherent.
+///
+/// Whole-object `get`/`get_opts` are intentionally *not* cached: their results
+/// are streams, so there is no cheap `Bytes` to retain, and byte-range reads
+/// (which ZeroFS issues for chunk access) are the hot path this cache targets.
+pub struct CachingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    shared: Arc<CacheShared>,
+}
+
+impl CachingObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, limit_bytes: u64) -> Self {
+        Self {
+            inner,
+            shared: Arc::new(CacheShared {
+                cache: Mutex::new(RangeCache::new(limit_bytes)),
+                heads: Mutex::new(HashMap::new()),
+                limit: limit_bytes,
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Snapshot the current hit/miss counters and occupancy.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.shared.hits.load(Ordering::Relaxed),
+            misses: self.shared.misses.load(Ordering::Relaxed),
+            cached_bytes: self.shared.cache.lock().bytes,
+            limit_bytes: self.shared.limit,
+        }
+    }
+
+    fn invalidate(&self, location: &Path) {
+        self.shared.invalidate(location);
+    }
+}
+
+/// Wraps an inner multipart upload so completing it invalidates the cached
+/// ranges/head for the target path — the object only lands on `complete`, so
+/// invalidating at `put_multipart` time would leave stale ranges re-cached by
+/// any concurrent read before the write finishes.
+#[derive(Debug)]
+struct InvalidatingMultipart {
+    inner: Box<dyn MultipartUpload>,
+    shared: Arc<CacheShared>,
+    location: Path,
+}
+
+impl MultipartUpload for InvalidatingMultipart {
+    fn put_part(&mut self, data: PutPayload) -> object_store::UploadPart {
+        self.inner.put_part(data)
+    }
+
+    fn complete(&mut self) -> futures::future::BoxFuture<'static, Result<PutResult>> {
+        let fut = self.inner.complete();
+        let shared = self.shared.clone();
+        let location = self.location.clone();
+        Box::pin(async move {
+            let result = fut.await;
+            shared.invalidate(&location);
+            result
+        })
+    }
+
+    fn abort(&mut self) -> futures::future::BoxFuture<'static, Result<()>> {
+        self.inner.abort()
+    }
+}
+
+impl Debug for CachingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CachingObjectStore({:?})", self.inner)
+    }
+}
+
+impl Display for CachingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CachingObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachingObjectStore {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        let result = self.inner.put(location, payload).await;
+        self.invalidate(location);
+        result
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        let result = self.inner.put_opts(location, payload, opts).await;
+        self.invalidate(location);
+        result
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        let inner = self.inner.put_multipart(location).await?;
+        Ok(Box::new(InvalidatingMultipart {
+            inner,
+            shared: self.shared.clone(),
+            location: location.clone(),
+        }))
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        let inner = self.inner.put_multipart_opts(location, opts).await?;
+        Ok(Box::new(InvalidatingMultipart {
+            inner,
+            shared: self.shared.clone(),
+            location: location.clone(),
+        }))
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        // Whole-object reads are streamed, so they are passed through uncached
+        // (see the type doc); only ranged reads and `head` are cached.
+        self.inner.get(location).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes> {
+        let key = (location.clone(), range.clone());
+        if let Some(bytes) = self.shared.cache.lock().get(&key) {
+            self.shared.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(bytes);
+        }
+        self.shared.misses.fetch_add(1, Ordering::Relaxed);
+        let bytes = self.inner.get_range(location, range).await?;
+        self.shared.cache.lock().insert(key, bytes.clone());
+        Ok(bytes)
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[std::ops::Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        let mut out: Vec<Option<Bytes>> = Vec::with_capacity(ranges.len());
+        let mut missing: Vec<(usize, std::ops::Range<u64>)> = Vec::new();
+        {
+            let mut cache = self.shared.cache.lock();
+            for (idx, range) in ranges.iter().enumerate() {
+                let key = (location.clone(), range.clone());
+                if let Some(bytes) = cache.get(&key) {
+                    self.shared.hits.fetch_add(1, Ordering::Relaxed);
+                    out.push(Some(bytes));
+                } else {
+                    self.shared.misses.fetch_add(1, Ordering::Relaxed);
+                    out.push(None);
+                    missing.push((idx, range.clone()));
+                }
+            }
+        }
+        if !missing.is_empty() {
+            let fetch: Vec<std::ops::Range<u64>> =
+                missing.iter().map(|(_, r)| r.clone()).collect();
+            let fetched = self.inner.get_ranges(location, &fetch).await?;
+            let mut cache = self.shared.cache.lock();
+            for ((idx, range), bytes) in missing.into_iter().zip(fetched) {
+                cache.insert((location.clone(), range), bytes.clone());
+                out[idx] = Some(bytes);
+            }
+        }
+        Ok(out.into_iter().map(|b| b.unwrap()).collect())
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        if let Some(meta) = self.shared.heads.lock().get(location).cloned() {
+            self.shared.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(meta);
+        }
+        self.shared.misses.fetch_add(1, Ordering::Relaxed);
+        let meta = self.inner.head(location).await?;
+        self.shared
+            .heads
+            .lock()
+            .insert(location.clone(), meta.clone());
+        Ok(meta)
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        let result = self.inner.delete(location).await;
+        self.invalidate(location);
+        result
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let result = self.inner.copy(from, to).await;
+        self.invalidate(to);
+        result
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let result = self.inner.rename(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let result = self.inner.copy_if_not_exists(from, to).await;
+        self.invalidate(to);
+        result
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let result = self.inner.rename_if_not_exists(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+}
@@ -0,0 +1,456 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use object_store::{
+    Error, GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta,
+    ObjectStore, PutMultipartOptions, PutOptions, PutPayload, PutResult, Result, UploadPart,
+    path::Path,
+};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+/// AES-256-GCM nonce length.
+const NONCE_LEN: usize = 12;
+/// AES-256-GCM authentication tag length.
+const TAG_LEN: usize = 16;
+/// Plaintext bytes per encrypted frame. Objects are split into fixed-size
+/// frames so an arbitrary logical range can be decrypted by reading only the
+/// frames it overlaps rather than the whole object.
+const FRAME_PLAIN: usize = 64 * 1024;
+/// On-disk size of a full frame: nonce || ciphertext || tag.
+const FRAME_CIPHER: usize = NONCE_LEN + FRAME_PLAIN + TAG_LEN;
+
+/// Supplies the AEAD key material for an object.
+///
+/// A provider may return a single static key or, for envelope encryption,
+/// derive/unwrap a per-object data key so keys can rotate without re-reading
+/// existing ciphertext. The same key must be returned for a given `location`
+/// across its lifetime or previously written frames become undecryptable.
+pub trait DataKeyProvider: Debug + Send + Sync {
+    fn data_key(&self, location: &Path) -> Result<[u8; 32]>;
+}
+
+/// A [`DataKeyProvider`] backed by one fixed 256-bit key.
+#[derive(Clone)]
+pub struct StaticDataKey {
+    key: [u8; 32],
+}
+
+impl StaticDataKey {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+}
+
+impl Debug for StaticDataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material.
+        write!(f, "StaticDataKey(..)")
+    }
+}
+
+impl DataKeyProvider for StaticDataKey {
+    fn data_key(&self, _: &Path) -> Result<[u8; 32]> {
+        Ok(self.key)
+    }
+}
+
+fn crypto_err(source: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::Generic {
+        store: "EncryptingObjectStore",
+        source: Box::new(source),
+    }
+}
+
+/// Encrypt one plaintext frame into `nonce || ciphertext || tag`.
+fn encrypt_frame(cipher: &Aes256Gcm, plaintext: &[u8]) -> Result<Bytes> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &[],
+            },
+        )
+        .map_err(crypto_err)?;
+    let mut out = BytesMut::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out.freeze())
+}
+
+/// Decrypt one on-disk frame produced by [`encrypt_frame`].
+fn decrypt_frame(cipher: &Aes256Gcm, frame: &[u8]) -> Result<Vec<u8>> {
+    if frame.len() < NONCE_LEN + TAG_LEN {
+        return Err(Error::Generic {
+            store: "EncryptingObjectStore",
+            source: "truncated encrypted frame".into(),
+        });
+    }
+    let (nonce, body) = frame.split_at(NONCE_LEN);
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: body,
+                aad: &[],
+            },
+        )
+        .map_err(crypto_err)
+}
+
+/// Map a ciphertext object length back to its plaintext length.
+fn plaintext_len(cipher_len: u64) -> u64 {
+    let full = cipher_len / FRAME_CIPHER as u64;
+    let rem = cipher_len % FRAME_CIPHER as u64;
+    let tail = rem.saturating_sub((NONCE_LEN + TAG_LEN) as u64);
+    full * FRAME_PLAIN as u64 + tail
+}
+
+/// ObjectStore decorator that transparently encrypts object bodies with a
+/// framed AEAD so payloads are confidential on untrusted backends, independent
+/// of any server-side encryption.
+pub struct EncryptingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    provider: Arc<dyn DataKeyProvider>,
+}
+
+impl EncryptingObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, provider: Arc<dyn DataKeyProvider>) -> Self {
+        Self { inner, provider }
+    }
+
+    fn cipher_for(&self, location: &Path) -> Result<Aes256Gcm> {
+        let key = self.provider.data_key(location)?;
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+    }
+
+    /// Encrypt a whole payload into a concatenation of fixed-size frames.
+    fn encrypt_payload(&self, location: &Path, payload: &PutPayload) -> Result<PutPayload> {
+        let cipher = self.cipher_for(location)?;
+        let plaintext: Vec<u8> = payload.iter().flat_map(|b| b.to_vec()).collect();
+        let mut out = BytesMut::new();
+        for chunk in plaintext.chunks(FRAME_PLAIN) {
+            out.extend_from_slice(&encrypt_frame(&cipher, chunk)?);
+        }
+        Ok(PutPayload::from_bytes(out.freeze()))
+    }
+
+    /// Decrypt an inclusive run of ciphertext frames `[first, last]` and return
+    /// the concatenated plaintext they cover.
+    async fn decrypt_frames(
+        &self,
+        location: &Path,
+        cipher: &Aes256Gcm,
+        first: u64,
+        last: u64,
+        cipher_len: u64,
+    ) -> Result<Vec<u8>> {
+        let ranges: Vec<std::ops::Range<u64>> = (first..=last)
+            .map(|i| {
+                let start = i * FRAME_CIPHER as u64;
+                // The final frame may be short; clamp its range to the object.
+                let end = (start + FRAME_CIPHER as u64).min(cipher_len);
+                start..end
+            })
+            .collect();
+        let frames = self.inner.get_ranges(location, &ranges).await?;
+        let mut plain = Vec::with_capacity(frames.len() * FRAME_PLAIN);
+        for frame in &frames {
+            plain.extend_from_slice(&decrypt_frame(cipher, frame)?);
+        }
+        Ok(plain)
+    }
+
+    /// Read the plaintext range `[start, end)` by decrypting only the frames
+    /// it overlaps, given an already-prepared cipher and the ciphertext length.
+    /// Callers that issue several ranges share a single `head` round trip this
+    /// way rather than paying one per range.
+    async fn read_range_with(
+        &self,
+        location: &Path,
+        cipher: &Aes256Gcm,
+        start: u64,
+        end: u64,
+        cipher_len: u64,
+    ) -> Result<Bytes> {
+        if end <= start {
+            return Ok(Bytes::new());
+        }
+        let first = start / FRAME_PLAIN as u64;
+        let last = (end - 1) / FRAME_PLAIN as u64;
+        let plain = self
+            .decrypt_frames(location, cipher, first, last, cipher_len)
+            .await?;
+        // Offset of the first decrypted frame within the plaintext.
+        let base = first * FRAME_PLAIN as u64;
+        let lo = ((start - base) as usize).min(plain.len());
+        let hi = ((end - base) as usize).min(plain.len());
+        Ok(Bytes::copy_from_slice(&plain[lo..hi]))
+    }
+
+    /// Read the plaintext range `[start, end)`, fetching the ciphertext length
+    /// with a single `head`.
+    async fn read_range(&self, location: &Path, start: u64, end: u64) -> Result<Bytes> {
+        let cipher = self.cipher_for(location)?;
+        let cipher_len = self.inner.head(location).await?.size;
+        self.read_range_with(location, &cipher, start, end, cipher_len)
+            .await
+    }
+
+    /// Read and decrypt an entire object.
+    async fn read_all(&self, location: &Path) -> Result<Bytes> {
+        let cipher = self.cipher_for(location)?;
+        let ciphertext = self.inner.get(location).await?.bytes().await?;
+        let mut plain = BytesMut::new();
+        for frame in ciphertext.chunks(FRAME_CIPHER) {
+            plain.extend_from_slice(&decrypt_frame(&cipher, frame)?);
+        }
+        Ok(plain.freeze())
+    }
+}
+
+impl Debug for EncryptingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EncryptingObjectStore({:?})", self.inner)
+    }
+}
+
+impl Display for EncryptingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EncryptingObjectStore({})", self.inner)
+    }
+}
+
+/// Multipart upload that encrypts into the same fixed-size frame layout as a
+/// single PUT, so objects written in parts read back through the shared
+/// frame-indexed [`read_range`](EncryptingObjectStore::read_range) path.
+///
+/// Every on-disk frame except the object's very last must be exactly
+/// `FRAME_PLAIN` bytes of plaintext. Rather than trusting callers to align
+/// their parts, this buffers any sub-frame trailing bytes (`leftover`) and
+/// prepends them to the next part, emitting only whole frames per part and
+/// flushing the short remainder as the final frame on `complete`.
+#[derive(Debug)]
+struct EncryptingMultipartUpload {
+    inner: Option<Box<dyn MultipartUpload>>,
+    cipher: Aes256Gcm,
+    leftover: Vec<u8>,
+}
+
+impl MultipartUpload for EncryptingMultipartUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let mut buf = std::mem::take(&mut self.leftover);
+        for chunk in data.iter() {
+            buf.extend_from_slice(chunk);
+        }
+        // Emit only whole frames; carry a sub-frame remainder to the next part.
+        let whole = (buf.len() / FRAME_PLAIN) * FRAME_PLAIN;
+        self.leftover = buf.split_off(whole);
+        if buf.is_empty() {
+            return Box::pin(async { Ok(()) });
+        }
+        let mut framed = BytesMut::new();
+        for chunk in buf.chunks(FRAME_PLAIN) {
+            match encrypt_frame(&self.cipher, chunk) {
+                Ok(frame) => framed.extend_from_slice(&frame),
+                Err(err) => return Box::pin(async move { Err(err) }),
+            }
+        }
+        match self.inner.as_mut() {
+            Some(inner) => inner.put_part(PutPayload::from_bytes(framed.freeze())),
+            None => Box::pin(async { Err(completed_err()) }),
+        }
+    }
+
+    fn complete(&mut self) -> BoxFuture<'static, Result<PutResult>> {
+        let Some(mut inner) = self.inner.take() else {
+            return Box::pin(async { Err(completed_err()) });
+        };
+        let leftover = std::mem::take(&mut self.leftover);
+        let cipher = self.cipher.clone();
+        Box::pin(async move {
+            // Flush the short trailing frame, if any, as the object's last part.
+            if !leftover.is_empty() {
+                let frame = encrypt_frame(&cipher, &leftover)?;
+                inner.put_part(PutPayload::from_bytes(frame)).await?;
+            }
+            inner.complete().await
+        })
+    }
+
+    fn abort(&mut self) -> BoxFuture<'static, Result<()>> {
+        match self.inner.take() {
+            Some(mut inner) => Box::pin(async move { inner.abort().await }),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+}
+
+fn completed_err() -> Error {
+    Error::Generic {
+        store: "EncryptingObjectStore",
+        source: "multipart upload already completed or aborted".into(),
+    }
+}
+
+#[async_trait]
+impl ObjectStore for EncryptingObjectStore {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        let payload = self.encrypt_payload(location, &payload)?;
+        self.inner.put(location, payload).await
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        let payload = self.encrypt_payload(location, &payload)?;
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        let cipher = self.cipher_for(location)?;
+        let inner = self.inner.put_multipart(location).await?;
+        Ok(Box::new(EncryptingMultipartUpload {
+            inner: Some(inner),
+            cipher,
+            leftover: Vec::new(),
+        }))
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        let cipher = self.cipher_for(location)?;
+        let inner = self.inner.put_multipart_opts(location, opts).await?;
+        Ok(Box::new(EncryptingMultipartUpload {
+            inner: Some(inner),
+            cipher,
+            leftover: Vec::new(),
+        }))
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        let meta = self.head(location).await?;
+        let bytes = self.read_all(location).await?;
+        let range = 0..bytes.len() as u64;
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(Box::pin(futures::stream::once(async move {
+                Ok(bytes)
+            }))),
+            meta,
+            range,
+            attributes: Default::default(),
+        })
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        // Honor a requested byte range by mapping it onto the plaintext; other
+        // options fall back to a full decrypt.
+        if let Some(range) = options.range.as_ref() {
+            // One `head` resolves both the plaintext-size metadata we return
+            // and the ciphertext length `read_range_with` needs.
+            let cipher = self.cipher_for(location)?;
+            let mut meta = self.inner.head(location).await?;
+            let cipher_len = meta.size;
+            meta.size = plaintext_len(cipher_len);
+            let (start, end) = match range {
+                object_store::GetRange::Bounded(r) => (r.start, r.end.min(meta.size)),
+                object_store::GetRange::Offset(o) => (*o, meta.size),
+                object_store::GetRange::Suffix(n) => (meta.size.saturating_sub(*n), meta.size),
+            };
+            let bytes = self
+                .read_range_with(location, &cipher, start, end, cipher_len)
+                .await?;
+            let range = start..start + bytes.len() as u64;
+            return Ok(GetResult {
+                payload: GetResultPayload::Stream(Box::pin(futures::stream::once(
+                    async move { Ok(bytes) },
+                ))),
+                meta,
+                range,
+                attributes: Default::default(),
+            });
+        }
+        self.get(location).await
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes> {
+        self.read_range(location, range.start, range.end).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[std::ops::Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        // Resolve the cipher and ciphertext length once and share them across
+        // every range rather than issuing a `head` per range.
+        let cipher = self.cipher_for(location)?;
+        let cipher_len = self.inner.head(location).await?.size;
+        let mut out = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            out.push(
+                self.read_range_with(location, &cipher, range.start, range.end, cipher_len)
+                    .await?,
+            );
+        }
+        Ok(out)
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        let mut meta = self.inner.head(location).await?;
+        meta.size = plaintext_len(meta.size);
+        Ok(meta)
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta>> {
+        // Sizes reported here remain ciphertext sizes; callers needing the
+        // plaintext length should `head` the object.
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
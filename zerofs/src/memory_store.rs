@@ -0,0 +1,470 @@
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use object_store::{
+    Error, GetOptions, GetRange, GetResult, GetResultPayload, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, PutMode, PutMultipartOptions, PutOptions, PutPayload, PutResult,
+    UploadPart, path::Path,
+};
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Flatten a [`PutPayload`] into a single contiguous buffer.
+fn payload_to_bytes(payload: &PutPayload) -> Bytes {
+    let mut buf = BytesMut::with_capacity(payload.content_length());
+    for chunk in payload.iter() {
+        buf.extend_from_slice(chunk);
+    }
+    buf.freeze()
+}
+
+/// Segment-aware prefix match matching `object_store::Path` semantics: a key
+/// matches `prefix` only when it equals `prefix` or continues past it at a
+/// delimiter boundary, so `foo` does not match `foobar`. Returns the remainder
+/// of the key after the prefix (with the leading delimiter stripped).
+fn strip_path_prefix<'a>(key: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return Some(key);
+    }
+    if key == prefix {
+        return Some("");
+    }
+    key.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('/'))
+}
+
+#[derive(Debug, Clone)]
+struct Stored {
+    data: Bytes,
+    last_modified: DateTime<Utc>,
+    e_tag: String,
+}
+
+/// A fully in-memory [`ObjectStore`] backed by a `BTreeMap`, suitable for unit
+/// testing ZeroFS's filesystem layer and the decorator chain without a real
+/// bucket. Supports correct `list_with_delimiter` semantics, atomic
+/// `*_if_not_exists`, and buffered multipart uploads.
+#[derive(Clone)]
+pub struct InMemoryObjectStore {
+    objects: Arc<RwLock<BTreeMap<Path, Stored>>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl Default for InMemoryObjectStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryObjectStore {
+    pub fn new() -> Self {
+        Self {
+            objects: Arc::new(RwLock::new(BTreeMap::new())),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn next_etag(&self) -> String {
+        let n = self.generation.fetch_add(1, Ordering::Relaxed);
+        format!("{n}")
+    }
+
+    fn store_bytes(&self, location: &Path, data: Bytes) -> PutResult {
+        let e_tag = self.next_etag();
+        let stored = Stored {
+            data,
+            last_modified: Utc::now(),
+            e_tag: e_tag.clone(),
+        };
+        self.objects.write().insert(location.clone(), stored);
+        PutResult {
+            e_tag: Some(e_tag),
+            version: None,
+        }
+    }
+
+    fn meta_of(location: &Path, stored: &Stored) -> ObjectMeta {
+        ObjectMeta {
+            location: location.clone(),
+            last_modified: stored.last_modified,
+            size: stored.data.len() as u64,
+            e_tag: Some(stored.e_tag.clone()),
+            version: None,
+        }
+    }
+
+    fn read(&self, location: &Path) -> Result<Stored, Error> {
+        self.objects
+            .read()
+            .get(location)
+            .cloned()
+            .ok_or_else(|| Error::NotFound {
+                path: location.to_string(),
+                source: "no such object".into(),
+            })
+    }
+}
+
+impl Debug for InMemoryObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "InMemoryObjectStore({} objects)", self.objects.read().len())
+    }
+}
+
+impl Display for InMemoryObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "InMemoryObjectStore")
+    }
+}
+
+/// Multipart upload that buffers parts in memory and writes the concatenation
+/// on completion.
+#[derive(Debug)]
+struct InMemoryMultipart {
+    store: InMemoryObjectStore,
+    location: Path,
+    parts: Vec<Bytes>,
+}
+
+impl MultipartUpload for InMemoryMultipart {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        self.parts.push(payload_to_bytes(&data));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn complete(&mut self) -> BoxFuture<'static, Result<PutResult, Error>> {
+        let parts = std::mem::take(&mut self.parts);
+        let store = self.store.clone();
+        let location = self.location.clone();
+        Box::pin(async move {
+            let mut buf = BytesMut::new();
+            for part in parts {
+                buf.extend_from_slice(&part);
+            }
+            Ok(store.store_bytes(&location, buf.freeze()))
+        })
+    }
+
+    fn abort(&mut self) -> BoxFuture<'static, Result<(), Error>> {
+        self.parts.clear();
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Slice an object for a requested byte range, clamping to the object length.
+fn slice_range(data: &Bytes, range: &GetRange) -> Bytes {
+    let len = data.len() as u64;
+    let (start, end) = match range {
+        GetRange::Bounded(r) => (r.start.min(len), r.end.min(len)),
+        GetRange::Offset(o) => ((*o).min(len), len),
+        GetRange::Suffix(n) => (len.saturating_sub(*n), len),
+    };
+    data.slice(start as usize..end as usize)
+}
+
+#[async_trait]
+impl ObjectStore for InMemoryObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult, Error> {
+        // Hold the write lock across the precondition check and the insert so
+        // conditional puts are atomic, mirroring a real bucket.
+        let mut guard = self.objects.write();
+        match &opts.mode {
+            PutMode::Create if guard.contains_key(location) => {
+                return Err(Error::AlreadyExists {
+                    path: location.to_string(),
+                    source: "object already exists".into(),
+                });
+            }
+            PutMode::Update(version) => match guard.get(location) {
+                Some(current) if version.e_tag.as_deref() == Some(current.e_tag.as_str()) => {}
+                _ => {
+                    return Err(Error::Precondition {
+                        path: location.to_string(),
+                        source: "e-tag precondition failed".into(),
+                    });
+                }
+            },
+            _ => {}
+        }
+        let e_tag = self.next_etag();
+        guard.insert(
+            location.clone(),
+            Stored {
+                data: payload_to_bytes(&payload),
+                last_modified: Utc::now(),
+                e_tag: e_tag.clone(),
+            },
+        );
+        Ok(PutResult {
+            e_tag: Some(e_tag),
+            version: None,
+        })
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        _opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>, Error> {
+        Ok(Box::new(InMemoryMultipart {
+            store: self.clone(),
+            location: location.clone(),
+            parts: Vec::new(),
+        }))
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult, Error> {
+        let stored = self.read(location)?;
+        let meta = Self::meta_of(location, &stored);
+        let (bytes, range) = match options.range {
+            Some(ref r) => {
+                let sliced = slice_range(&stored.data, r);
+                let start = match r {
+                    GetRange::Bounded(b) => b.start.min(stored.data.len() as u64),
+                    GetRange::Offset(o) => (*o).min(stored.data.len() as u64),
+                    GetRange::Suffix(n) => {
+                        (stored.data.len() as u64).saturating_sub(*n)
+                    }
+                };
+                (sliced.clone(), start..start + sliced.len() as u64)
+            }
+            None => (stored.data.clone(), 0..stored.data.len() as u64),
+        };
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(Box::pin(stream::once(async move { Ok(bytes) }))),
+            meta,
+            range,
+            attributes: Default::default(),
+        })
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes, Error> {
+        let stored = self.read(location)?;
+        Ok(slice_range(&stored.data, &GetRange::Bounded(range)))
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta, Error> {
+        let stored = self.read(location)?;
+        Ok(Self::meta_of(location, &stored))
+    }
+
+    async fn delete(&self, location: &Path) -> Result<(), Error> {
+        self.objects.write().remove(location);
+        Ok(())
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta, Error>> {
+        let prefix = prefix.cloned();
+        let metas: Vec<_> = self
+            .objects
+            .read()
+            .iter()
+            .filter(|(loc, _)| match &prefix {
+                Some(p) => strip_path_prefix(loc.as_ref(), p.as_ref()).is_some(),
+                None => true,
+            })
+            .map(|(loc, stored)| Ok(Self::meta_of(loc, stored)))
+            .collect();
+        stream::iter(metas).boxed()
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta, Error>> {
+        let offset = offset.clone();
+        let prefix = prefix.cloned();
+        let metas: Vec<_> = self
+            .objects
+            .read()
+            .iter()
+            .filter(|(loc, _)| *loc > &offset)
+            .filter(|(loc, _)| match &prefix {
+                Some(p) => strip_path_prefix(loc.as_ref(), p.as_ref()).is_some(),
+                None => true,
+            })
+            .map(|(loc, stored)| Ok(Self::meta_of(loc, stored)))
+            .collect();
+        stream::iter(metas).boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult, Error> {
+        let prefix_str = prefix.map(|p| p.as_ref().to_string()).unwrap_or_default();
+        let mut objects = Vec::new();
+        let mut common_prefixes = std::collections::BTreeSet::new();
+        let guard = self.objects.read();
+        for (loc, stored) in guard.iter() {
+            let key = loc.as_ref();
+            let Some(rest) = strip_path_prefix(key, &prefix_str) else {
+                continue;
+            };
+            match rest.find('/') {
+                Some(idx) => {
+                    let segment = &rest[..idx];
+                    let cp = if prefix_str.is_empty() {
+                        segment.to_string()
+                    } else {
+                        format!("{prefix_str}/{segment}")
+                    };
+                    common_prefixes.insert(Path::from(cp));
+                }
+                None => objects.push(Self::meta_of(loc, stored)),
+            }
+        }
+        Ok(ListResult {
+            common_prefixes: common_prefixes.into_iter().collect(),
+            objects,
+        })
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<(), Error> {
+        let stored = self.read(from)?;
+        self.store_bytes(to, stored.data);
+        Ok(())
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<(), Error> {
+        let mut guard = self.objects.write();
+        if guard.contains_key(to) {
+            return Err(Error::AlreadyExists {
+                path: to.to_string(),
+                source: "destination already exists".into(),
+            });
+        }
+        let stored = guard.get(from).cloned().ok_or_else(|| Error::NotFound {
+            path: from.to_string(),
+            source: "no such object".into(),
+        })?;
+        let e_tag = self.next_etag();
+        guard.insert(
+            to.clone(),
+            Stored {
+                data: stored.data,
+                last_modified: Utc::now(),
+                e_tag,
+            },
+        );
+        Ok(())
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<(), Error> {
+        // Check, insert and remove under one write lock so the rename is atomic.
+        let mut guard = self.objects.write();
+        if guard.contains_key(to) {
+            return Err(Error::AlreadyExists {
+                path: to.to_string(),
+                source: "destination already exists".into(),
+            });
+        }
+        let stored = guard.remove(from).ok_or_else(|| Error::NotFound {
+            path: from.to_string(),
+            source: "no such object".into(),
+        })?;
+        guard.insert(to.clone(), stored);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caching_wrapper::CachingObjectStore;
+    use crate::encrypting_wrapper::{EncryptingObjectStore, StaticDataKey};
+    use crate::retry_wrapper::{RetryConfig, RetryObjectStore};
+
+    #[tokio::test]
+    async fn in_memory_round_trip() {
+        let store = InMemoryObjectStore::new();
+        store
+            .put(&Path::from("a/b/1"), PutPayload::from_static(b"hello world"))
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("a/b/2"), PutPayload::from_static(b"second"))
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("a/c"), PutPayload::from_static(b"leaf"))
+            .await
+            .unwrap();
+
+        // Whole-object and ranged reads.
+        let got = store.get(&Path::from("a/b/1")).await.unwrap();
+        assert_eq!(&got.bytes().await.unwrap()[..], b"hello world");
+        let range = store.get_range(&Path::from("a/b/1"), 0..5).await.unwrap();
+        assert_eq!(&range[..], b"hello");
+
+        // Delimited listing rolls up nested keys into common prefixes and
+        // respects segment boundaries (prefix "a" must not match "ab/...").
+        store
+            .put(&Path::from("ab/x"), PutPayload::from_static(b"decoy"))
+            .await
+            .unwrap();
+        let result = store
+            .list_with_delimiter(Some(&Path::from("a")))
+            .await
+            .unwrap();
+        assert_eq!(result.common_prefixes, vec![Path::from("a/b")]);
+        let objects: Vec<_> = result.objects.iter().map(|m| m.location.clone()).collect();
+        assert_eq!(objects, vec![Path::from("a/c")]);
+
+        // copy_if_not_exists is atomic and refuses an existing destination.
+        store
+            .copy_if_not_exists(&Path::from("a/c"), &Path::from("a/d"))
+            .await
+            .unwrap();
+        assert_eq!(
+            &store.get(&Path::from("a/d")).await.unwrap().bytes().await.unwrap()[..],
+            b"leaf"
+        );
+        let err = store
+            .copy_if_not_exists(&Path::from("a/c"), &Path::from("a/d"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::AlreadyExists { .. }));
+    }
+
+    #[tokio::test]
+    async fn decorator_chain_round_trip() {
+        // Retry -> Caching -> Encrypting -> InMemory, exercised end to end.
+        let inner = Arc::new(InMemoryObjectStore::new());
+        let key = StaticDataKey::new([7u8; 32]);
+        let encrypting = Arc::new(EncryptingObjectStore::new(inner, Arc::new(key)));
+        let caching = Arc::new(CachingObjectStore::new(encrypting, 1 << 20));
+        let chain = RetryObjectStore::new(caching.clone(), RetryConfig::default());
+
+        // A payload spanning more than one frame to exercise the framed layout.
+        let data = vec![0xABu8; 200 * 1024];
+        let path = Path::from("data/chunk");
+        chain
+            .put(&path, PutPayload::from(Bytes::from(data.clone())))
+            .await
+            .unwrap();
+
+        let whole = chain.get(&path).await.unwrap().bytes().await.unwrap();
+        assert_eq!(whole.len(), data.len());
+        assert_eq!(&whole[..], &data[..]);
+
+        // A cross-frame range, read twice so the cache serves the second hit.
+        let range = chain.get_range(&path, 100_000..150_000).await.unwrap();
+        assert_eq!(&range[..], &data[100_000..150_000]);
+        let again = chain.get_range(&path, 100_000..150_000).await.unwrap();
+        assert_eq!(&again[..], &data[100_000..150_000]);
+        assert!(caching.stats().hits >= 1);
+
+        // head reports the plaintext length, not the ciphertext length.
+        assert_eq!(chain.head(&path).await.unwrap().size, data.len() as u64);
+    }
+}
@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult, Result, path::Path,
+};
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Upper bounds (in microseconds) of the latency histogram buckets. The final
+/// implicit bucket captures everything slower than the last bound.
+const LATENCY_BOUNDS_US: [u64; 7] = [100, 500, 1_000, 5_000, 10_000, 50_000, 100_000];
+
+/// A fixed-bucket latency histogram recorded with atomics.
+#[derive(Debug)]
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BOUNDS_US.len() + 1],
+    sum_us: AtomicU64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum_us: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Histogram {
+    fn record(&self, elapsed_us: u64) {
+        let idx = LATENCY_BOUNDS_US
+            .iter()
+            .position(|bound| elapsed_us <= *bound)
+            .unwrap_or(LATENCY_BOUNDS_US.len());
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(elapsed_us, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            bounds_us: LATENCY_BOUNDS_US,
+            buckets: std::array::from_fn(|i| self.buckets[i].load(Ordering::Relaxed)),
+            sum_us: self.sum_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Per-method counters: call count, bytes moved, and a latency histogram.
+#[derive(Debug, Default)]
+struct MethodMetrics {
+    calls: AtomicU64,
+    bytes: AtomicU64,
+    latency: Histogram,
+}
+
+impl MethodMetrics {
+    fn record(&self, bytes: u64, elapsed_us: u64) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.latency.record(elapsed_us);
+    }
+
+    fn snapshot(&self) -> MethodSnapshot {
+        MethodSnapshot {
+            calls: self.calls.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            latency: self.latency.snapshot(),
+        }
+    }
+}
+
+/// Point-in-time copy of a latency histogram.
+#[derive(Debug, Clone)]
+pub struct HistogramSnapshot {
+    pub bounds_us: [u64; LATENCY_BOUNDS_US.len()],
+    pub buckets: [u64; LATENCY_BOUNDS_US.len() + 1],
+    pub sum_us: u64,
+}
+
+/// Point-in-time copy of one method's metrics.
+#[derive(Debug, Clone)]
+pub struct MethodSnapshot {
+    pub calls: u64,
+    pub bytes: u64,
+    pub latency: HistogramSnapshot,
+}
+
+/// A snapshot of all recorded metrics, one entry per instrumented method.
+#[derive(Debug, Clone)]
+pub struct MetricsSnapshot {
+    pub put: MethodSnapshot,
+    pub get: MethodSnapshot,
+    pub get_range: MethodSnapshot,
+    pub head: MethodSnapshot,
+    pub delete: MethodSnapshot,
+    pub list: MethodSnapshot,
+    pub copy: MethodSnapshot,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    put: MethodMetrics,
+    get: MethodMetrics,
+    get_range: MethodMetrics,
+    head: MethodMetrics,
+    delete: MethodMetrics,
+    list: MethodMetrics,
+    copy: MethodMetrics,
+}
+
+/// ObjectStore decorator that records per-method call counts, byte volumes and
+/// latency histograms, exposing a [`MetricsSnapshot`] for observability.
+pub struct MetricsObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    metrics: Metrics,
+}
+
+impl MetricsObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Snapshot every counter for export to a metrics system.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            put: self.metrics.put.snapshot(),
+            get: self.metrics.get.snapshot(),
+            get_range: self.metrics.get_range.snapshot(),
+            head: self.metrics.head.snapshot(),
+            delete: self.metrics.delete.snapshot(),
+            list: self.metrics.list.snapshot(),
+            copy: self.metrics.copy.snapshot(),
+        }
+    }
+}
+
+impl Debug for MetricsObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MetricsObjectStore({:?})", self.inner)
+    }
+}
+
+impl Display for MetricsObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MetricsObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for MetricsObjectStore {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        let bytes = payload.content_length() as u64;
+        let start = Instant::now();
+        let result = self.inner.put(location, payload).await;
+        self.metrics.put.record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        let bytes = payload.content_length() as u64;
+        let start = Instant::now();
+        let result = self.inner.put_opts(location, payload, opts).await;
+        self.metrics.put.record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart(location).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        let start = Instant::now();
+        let result = self.inner.get(location).await;
+        let bytes = result.as_ref().map(|r| r.meta.size).unwrap_or(0);
+        self.metrics.get.record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        let start = Instant::now();
+        let result = self.inner.get_opts(location, options).await;
+        // A ranged read moves only the returned range, not the whole object.
+        let bytes = result
+            .as_ref()
+            .map(|r| r.range.end - r.range.start)
+            .unwrap_or(0);
+        self.metrics.get.record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes> {
+        let start = Instant::now();
+        let result = self.inner.get_range(location, range).await;
+        let bytes = result.as_ref().map(|b| b.len() as u64).unwrap_or(0);
+        self.metrics
+            .get_range
+            .record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[std::ops::Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        let start = Instant::now();
+        let result = self.inner.get_ranges(location, ranges).await;
+        let bytes = result
+            .as_ref()
+            .map(|v| v.iter().map(|b| b.len() as u64).sum())
+            .unwrap_or(0);
+        self.metrics
+            .get_range
+            .record(bytes, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        let start = Instant::now();
+        let result = self.inner.head(location).await;
+        self.metrics.head.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.delete(location).await;
+        self.metrics
+            .delete
+            .record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.metrics.list.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.metrics.list.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        let start = Instant::now();
+        let result = self.inner.list_with_delimiter(prefix).await;
+        self.metrics.list.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.copy(from, to).await;
+        self.metrics.copy.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.rename(from, to).await;
+        self.metrics.copy.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.copy_if_not_exists(from, to).await;
+        self.metrics.copy.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.rename_if_not_exists(from, to).await;
+        self.metrics.copy.record(0, start.elapsed().as_micros() as u64);
+        result
+    }
+}
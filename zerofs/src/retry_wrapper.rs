@@ -0,0 +1,227 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::{
+    Error, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult, Result, path::Path,
+};
+use rand::Rng;
+use std::fmt::{Debug, Display};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::time::{Duration, Instant, sleep};
+
+/// Backoff configuration for [`RetryObjectStore`].
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Base delay for the first retry; doubles each attempt up to `cap`.
+    pub initial: Duration,
+    /// Upper bound on the backoff base before jitter.
+    pub cap: Duration,
+    /// Maximum number of retries after the initial attempt.
+    pub max_retries: u32,
+    /// Give up once this much time has elapsed across all attempts.
+    pub deadline: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            cap: Duration::from_secs(10),
+            max_retries: 5,
+            deadline: Duration::from_secs(60),
+        }
+    }
+}
+
+/// `true` for transient failures worth retrying — timeouts, 5xx, connection
+/// resets and other generic transport errors. `AlreadyExists`, `NotFound` and
+/// `Precondition` are semantically meaningful to ZeroFS's `copy_if_not_exists`
+/// locking and must never be retried, nor should the other deterministic
+/// client errors.
+fn is_retryable(err: &Error) -> bool {
+    !matches!(
+        err,
+        Error::NotFound { .. }
+            | Error::AlreadyExists { .. }
+            | Error::Precondition { .. }
+            | Error::NotModified { .. }
+            | Error::NotSupported { .. }
+            | Error::NotImplemented
+            | Error::PermissionDenied { .. }
+            | Error::Unauthenticated { .. }
+            | Error::InvalidPath { .. }
+            | Error::UnknownConfigurationKey { .. }
+    )
+}
+
+/// ObjectStore decorator that transparently retries transient failures on all
+/// idempotent methods using full-jitter exponential backoff.
+pub struct RetryObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    config: RetryConfig,
+}
+
+impl RetryObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, config: RetryConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Run `op`, retrying transient errors with full-jitter backoff until it
+    /// succeeds, an error is non-retryable, the retry budget is exhausted, or
+    /// the overall deadline passes.
+    async fn retry<T, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let start = Instant::now();
+        let mut attempt: u32 = 0;
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    if attempt >= self.config.max_retries
+                        || !is_retryable(&err)
+                        || start.elapsed() >= self.config.deadline
+                    {
+                        return Err(err);
+                    }
+                    // base = min(cap, initial * 2^attempt), guarding overflow.
+                    let factor = 2u32.saturating_pow(attempt);
+                    let base = self
+                        .config
+                        .initial
+                        .saturating_mul(factor)
+                        .min(self.config.cap);
+                    let sleep_for = full_jitter(base);
+                    // Do not sleep past the deadline.
+                    let remaining = self
+                        .config
+                        .deadline
+                        .saturating_sub(start.elapsed());
+                    sleep(sleep_for.min(remaining)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// A uniformly random duration in `[0, base]` (full jitter), drawn from the
+/// thread RNG so concurrent retriers spread out independently.
+fn full_jitter(base: Duration) -> Duration {
+    let base_nanos = base.as_nanos() as u64;
+    if base_nanos == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_nanos(rand::thread_rng().gen_range(0..=base_nanos))
+}
+
+impl Debug for RetryObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RetryObjectStore({:?})", self.inner)
+    }
+}
+
+impl Display for RetryObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RetryObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for RetryObjectStore {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        self.retry(|| self.inner.put(location, payload.clone())).await
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        self.retry(|| self.inner.put_opts(location, payload.clone(), opts.clone()))
+            .await
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        self.retry(|| self.inner.put_multipart(location)).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        self.retry(|| self.inner.put_multipart_opts(location, opts.clone()))
+            .await
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        self.retry(|| self.inner.get(location)).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        self.retry(|| self.inner.get_opts(location, options.clone()))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes> {
+        self.retry(|| self.inner.get_range(location, range.clone()))
+            .await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[std::ops::Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        self.retry(|| self.inner.get_ranges(location, ranges)).await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        self.retry(|| self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.retry(|| self.inner.delete(location)).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta>> {
+        // Stream construction is lazy; per-item errors surface during polling
+        // and are left to the caller, matching the inner store's contract.
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.retry(|| self.inner.list_with_delimiter(prefix)).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.retry(|| self.inner.copy(from, to)).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.retry(|| self.inner.rename(from, to)).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.retry(|| self.inner.copy_if_not_exists(from, to)).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.retry(|| self.inner.rename_if_not_exists(from, to))
+            .await
+    }
+}
@@ -2,44 +2,165 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::BoxStream;
 use object_store::{
-    Attribute, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta,
+    Attribute, Attributes, GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta,
     ObjectStore, PutMultipartOptions, PutOptions, PutPayload, PutResult, Result,
     path::Path,
 };
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
 
-/// Wrapper around an ObjectStore that sets storage class on all PUT operations
+/// Decides which storage class (if any) a given object should be stored under.
+///
+/// Returning `None` leaves the PUT untouched so the backend falls back to its
+/// own default class; returning `Some(class)` stamps the request with that
+/// `Attribute::StorageClass`. Policies see the target `Path` (whose prefix
+/// distinguishes metadata from data key namespaces), the payload length when it
+/// is known, and any caller-supplied attributes already on the request.
+pub trait StorageClassPolicy: Debug + Send + Sync {
+    fn class_for(
+        &self,
+        location: &Path,
+        payload_len: Option<u64>,
+        attrs: &Attributes,
+    ) -> Option<String>;
+}
+
+/// Always stamps the same storage class, regardless of object.
+#[derive(Debug, Clone)]
+pub struct ConstantStorageClassPolicy {
+    class: String,
+}
+
+impl ConstantStorageClassPolicy {
+    pub fn new(class: impl Into<String>) -> Self {
+        Self {
+            class: class.into(),
+        }
+    }
+}
+
+impl StorageClassPolicy for ConstantStorageClassPolicy {
+    fn class_for(&self, _: &Path, _: Option<u64>, _: &Attributes) -> Option<String> {
+        Some(self.class.clone())
+    }
+}
+
+/// Routes objects by a prefix of their `Path`, e.g. to send the `metadata` key
+/// namespace to a standard tier and `data` to an archival one. Matching is a
+/// plain string prefix, not segment-aware: a rule for `data` also matches
+/// `database/...`.
+#[derive(Debug, Clone)]
+pub struct PrefixStorageClassPolicy {
+    rules: Vec<(String, String)>,
+    default_class: Option<String>,
+}
+
+impl PrefixStorageClassPolicy {
+    /// `rules` pairs a path prefix with the class to stamp when the location
+    /// starts with it; the first matching rule wins. `default_class` is used
+    /// when nothing matches.
+    pub fn new(rules: Vec<(String, String)>, default_class: Option<String>) -> Self {
+        Self {
+            rules,
+            default_class,
+        }
+    }
+}
+
+impl StorageClassPolicy for PrefixStorageClassPolicy {
+    fn class_for(&self, location: &Path, _: Option<u64>, _: &Attributes) -> Option<String> {
+        let key = location.as_ref();
+        for (prefix, class) in &self.rules {
+            if key.starts_with(prefix.as_str()) {
+                return Some(class.clone());
+            }
+        }
+        self.default_class.clone()
+    }
+}
+
+/// Stamps one class for objects at or above a byte threshold and another below
+/// it, so large cold chunks land on cheap archival tiers while small hot
+/// objects stay on standard. Objects with an unknown length fall back to the
+/// small class.
+#[derive(Debug, Clone)]
+pub struct SizeThresholdStorageClassPolicy {
+    threshold: u64,
+    large_class: Option<String>,
+    small_class: Option<String>,
+}
+
+impl SizeThresholdStorageClassPolicy {
+    pub fn new(
+        threshold: u64,
+        large_class: Option<String>,
+        small_class: Option<String>,
+    ) -> Self {
+        Self {
+            threshold,
+            large_class,
+            small_class,
+        }
+    }
+}
+
+impl StorageClassPolicy for SizeThresholdStorageClassPolicy {
+    fn class_for(&self, _: &Path, payload_len: Option<u64>, _: &Attributes) -> Option<String> {
+        match payload_len {
+            Some(len) if len >= self.threshold => self.large_class.clone(),
+            _ => self.small_class.clone(),
+        }
+    }
+}
+
+/// Wrapper around an ObjectStore that sets storage class on PUT operations
+/// according to a pluggable [`StorageClassPolicy`].
 pub struct StorageClassObjectStore {
     inner: Arc<dyn ObjectStore>,
-    storage_class: String,
+    policy: Arc<dyn StorageClassPolicy>,
 }
 
 impl StorageClassObjectStore {
+    /// Stamp every PUT with a single fixed class (the historical behavior).
     pub fn new(inner: Arc<dyn ObjectStore>, storage_class: String) -> Self {
-        Self {
-            inner,
-            storage_class,
-        }
+        Self::with_policy(inner, Arc::new(ConstantStorageClassPolicy::new(storage_class)))
+    }
+
+    /// Stamp PUTs according to `policy`, which may route objects to different
+    /// tiers by path prefix, size, or any custom rule.
+    pub fn with_policy(
+        inner: Arc<dyn ObjectStore>,
+        policy: Arc<dyn StorageClassPolicy>,
+    ) -> Self {
+        Self { inner, policy }
     }
 
-    fn add_storage_class_to_put(&self, mut opts: PutOptions) -> PutOptions {
-        let mut attrs = opts.attributes.clone();
-        attrs.insert(
-            Attribute::StorageClass,
-            self.storage_class.clone().into(),
-        );
-        opts.attributes = attrs;
+    fn add_storage_class_to_put(
+        &self,
+        location: &Path,
+        payload_len: Option<u64>,
+        mut opts: PutOptions,
+    ) -> PutOptions {
+        if let Some(class) = self.policy.class_for(location, payload_len, &opts.attributes) {
+            let mut attrs = opts.attributes.clone();
+            attrs.insert(Attribute::StorageClass, class.into());
+            opts.attributes = attrs;
+        }
         opts
     }
 
-    fn add_storage_class_to_multipart(&self, mut opts: PutMultipartOptions) -> PutMultipartOptions {
-        let mut attrs = opts.attributes.clone();
-        attrs.insert(
-            Attribute::StorageClass,
-            self.storage_class.clone().into(),
-        );
-        opts.attributes = attrs;
+    fn add_storage_class_to_multipart(
+        &self,
+        location: &Path,
+        mut opts: PutMultipartOptions,
+    ) -> PutMultipartOptions {
+        // Multipart uploads have no payload length up front, so the policy is
+        // consulted with `None`.
+        if let Some(class) = self.policy.class_for(location, None, &opts.attributes) {
+            let mut attrs = opts.attributes.clone();
+            attrs.insert(Attribute::StorageClass, class.into());
+            opts.attributes = attrs;
+        }
         opts
     }
 }
@@ -48,26 +169,23 @@ impl Debug for StorageClassObjectStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "StorageClassObjectStore({:?}, class={})",
-            self.inner, self.storage_class
+            "StorageClassObjectStore({:?}, policy={:?})",
+            self.inner, self.policy
         )
     }
 }
 
 impl Display for StorageClassObjectStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "StorageClassObjectStore({}, class={})",
-            self.inner, self.storage_class
-        )
+        write!(f, "StorageClassObjectStore({})", self.inner)
     }
 }
 
 #[async_trait]
 impl ObjectStore for StorageClassObjectStore {
     async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
-        let opts = self.add_storage_class_to_put(PutOptions::default());
+        let payload_len = Some(payload.content_length() as u64);
+        let opts = self.add_storage_class_to_put(location, payload_len, PutOptions::default());
         self.inner.put_opts(location, payload, opts).await
     }
 
@@ -77,12 +195,13 @@ impl ObjectStore for StorageClassObjectStore {
         payload: PutPayload,
         opts: PutOptions,
     ) -> Result<PutResult> {
-        let opts = self.add_storage_class_to_put(opts);
+        let payload_len = Some(payload.content_length() as u64);
+        let opts = self.add_storage_class_to_put(location, payload_len, opts);
         self.inner.put_opts(location, payload, opts).await
     }
 
     async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
-        let opts = self.add_storage_class_to_multipart(PutMultipartOptions::default());
+        let opts = self.add_storage_class_to_multipart(location, PutMultipartOptions::default());
         self.inner.put_multipart_opts(location, opts).await
     }
 
@@ -91,7 +210,7 @@ impl ObjectStore for StorageClassObjectStore {
         location: &Path,
         opts: PutMultipartOptions,
     ) -> Result<Box<dyn MultipartUpload>> {
-        let opts = self.add_storage_class_to_multipart(opts);
+        let opts = self.add_storage_class_to_multipart(location, opts);
         self.inner.put_multipart_opts(location, opts).await
     }
 
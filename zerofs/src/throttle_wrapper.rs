@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult, Result, path::Path,
+};
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant, sleep};
+
+/// A single token-bucket limiter shared across requests.
+///
+/// Tokens refill continuously at `rate` per second up to a `burst` ceiling.
+/// `acquire` blocks until the requested number of tokens is available; a cost
+/// larger than `burst` is still satisfied (it simply waits for a full bucket
+/// and drains it) so an oversized request can never deadlock.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            rate,
+            burst,
+            tokens: burst,
+            last: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        if elapsed > 0.0 {
+            self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+            self.last = now;
+        }
+    }
+}
+
+/// Wraps a bucket behind a `tokio::sync::Mutex` so the limiter can be shared
+/// across concurrent requests without blocking the async runtime.
+#[derive(Debug)]
+struct Limiter {
+    bucket: Mutex<TokenBucket>,
+}
+
+impl Limiter {
+    fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            bucket: Mutex::new(TokenBucket::new(rate, burst)),
+        }
+    }
+
+    /// Wait until `cost` tokens can be deducted, then deduct them.
+    ///
+    /// A `cost` larger than `burst` can never accumulate (refill caps the
+    /// bucket at `burst`), so it is clamped to `burst`: the request waits for a
+    /// full bucket, drains it, and proceeds rather than sleeping forever.
+    async fn acquire(&self, cost: f64) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().await;
+                let effective = cost.min(bucket.burst);
+                bucket.refill(Instant::now());
+                if bucket.tokens >= effective {
+                    bucket.tokens -= effective;
+                    return;
+                }
+                let deficit = effective - bucket.tokens;
+                Duration::from_secs_f64(deficit / bucket.rate)
+            };
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Configuration for [`ThrottledObjectStore`]. Each dimension is optional; an
+/// unset dimension adds zero overhead (no bucket, no lock).
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleConfig {
+    /// Maximum request operations per second, with a burst allowance.
+    pub ops: Option<(f64, f64)>,
+    /// Maximum bytes transferred per second, with a burst allowance.
+    pub bytes: Option<(f64, f64)>,
+}
+
+/// ObjectStore decorator that rate-limits operations and bandwidth using
+/// token buckets, to protect backends that 503 under the bursty small-chunk
+/// traffic ZeroFS generates.
+pub struct ThrottledObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    ops: Option<Limiter>,
+    bytes: Option<Limiter>,
+}
+
+impl ThrottledObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, config: ThrottleConfig) -> Self {
+        Self {
+            inner,
+            ops: config.ops.map(|(rate, burst)| Limiter::new(rate, burst)),
+            bytes: config.bytes.map(|(rate, burst)| Limiter::new(rate, burst)),
+        }
+    }
+
+    /// Charge a single operation token plus `bytes` bandwidth tokens.
+    async fn charge(&self, bytes: u64) {
+        if let Some(ops) = &self.ops {
+            ops.acquire(1.0).await;
+        }
+        if bytes > 0 {
+            if let Some(limiter) = &self.bytes {
+                limiter.acquire(bytes as f64).await;
+            }
+        }
+    }
+}
+
+impl Debug for ThrottledObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ThrottledObjectStore({:?})", self.inner)
+    }
+}
+
+impl Display for ThrottledObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ThrottledObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ThrottledObjectStore {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        self.charge(payload.content_length() as u64).await;
+        self.inner.put(location, payload).await
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        self.charge(payload.content_length() as u64).await;
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        self.charge(0).await;
+        self.inner.put_multipart(location).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        self.charge(0).await;
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        // Whole-object GET: size the bandwidth charge from a preceding head,
+        // but only when bandwidth is actually limited (the head costs a round
+        // trip, so it is not worth paying when unthrottled).
+        if self.bytes.is_some() {
+            let meta = self.head(location).await?;
+            self.charge(meta.size).await;
+        } else {
+            self.charge(0).await;
+        }
+        self.inner.get(location).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        if self.bytes.is_some() {
+            let meta = self.head(location).await?;
+            self.charge(meta.size).await;
+        } else {
+            self.charge(0).await;
+        }
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(&self, location: &Path, range: std::ops::Range<u64>) -> Result<Bytes> {
+        self.charge(range.end.saturating_sub(range.start)).await;
+        self.inner.get_range(location, range).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[std::ops::Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        let bytes: u64 = ranges
+            .iter()
+            .map(|r| r.end.saturating_sub(r.start))
+            .sum();
+        self.charge(bytes).await;
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.charge(0).await;
+        self.inner.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.charge(0).await;
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.charge(0).await;
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.charge(0).await;
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.charge(0).await;
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.charge(0).await;
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}